@@ -0,0 +1,167 @@
+//! A lossless, full-fidelity layer on top of `Tokenizer`.
+//!
+//! `Tokenizer` already distinguishes significant `LexicalToken`s from `HiddenToken`s
+//! (whitespace and comments), but a formatter or refactoring tool that wants to print
+//! code back out has to manually re-thread the hidden tokens between the significant
+//! ones. `CstTokens` does that threading once, grouping each `LexicalToken` together
+//! with the hidden tokens attached to it into a [`CstToken`] node.
+//!
+//! Attachment rule: trailing whitespace/comments up to and including the next newline
+//! attach to the *preceding* token; any trivia remaining after that newline attaches as
+//! leading trivia to the *following* token. This matches where a human would expect a
+//! trailing same-line comment versus a leading doc comment to belong. Trivia after the
+//! last token's newline has no following token and is kept in the last token's trailing
+//! trivia instead.
+use std::fmt;
+
+use crate::hidden_token::HiddenToken;
+use crate::lexical_token::LexicalToken;
+use crate::tokens::Whitespace;
+use crate::{Result, Token, Tokenizer};
+
+/// A `LexicalToken` together with the leading and trailing [`HiddenToken`]s attached to
+/// it.
+///
+/// `leading_trivia().iter().map(HiddenToken::text).collect::<String>() + token().text() +
+/// trailing_trivia().iter().map(HiddenToken::text).collect::<String>()` (i.e. `to_source`)
+/// reproduces this node's exact span of the original source; doing so for every node
+/// yielded by a `CstTokens` reproduces the whole input, provided the input contains at
+/// least one significant token (an input of only whitespace and comments yields no nodes).
+#[derive(Debug, Clone, PartialEq)]
+pub struct CstToken {
+    leading: Vec<HiddenToken>,
+    token: LexicalToken,
+    trailing: Vec<HiddenToken>,
+}
+impl CstToken {
+    /// Returns the hidden tokens that precede this token: those after the newline that
+    /// ended the previous token's trailing trivia, or, for the first token, every hidden
+    /// token before it.
+    pub fn leading_trivia(&self) -> &[HiddenToken] {
+        &self.leading
+    }
+
+    /// Returns the significant token this node is built around.
+    pub fn token(&self) -> &LexicalToken {
+        &self.token
+    }
+
+    /// Returns the hidden tokens that trail this token, up to and including the next
+    /// newline (if any before the next significant token). For the last token, this also
+    /// holds any trivia after that newline up to the end of the input.
+    pub fn trailing_trivia(&self) -> &[HiddenToken] {
+        &self.trailing
+    }
+
+    /// Reconstructs this node's exact span of the original source text.
+    pub fn to_source(&self) -> String {
+        let mut source = String::new();
+        for hidden in &self.leading {
+            source.push_str(hidden.text());
+        }
+        source.push_str(self.token.text());
+        for hidden in &self.trailing {
+            source.push_str(hidden.text());
+        }
+        source
+    }
+}
+impl fmt::Display for CstToken {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.to_source())
+    }
+}
+
+/// An iterator that groups the tokens of an Erlang source text into [`CstToken`] nodes.
+///
+/// See the module documentation for the trivia-attachment rule.
+#[derive(Debug)]
+pub struct CstTokens<'a> {
+    tokenizer: Tokenizer<'a>,
+    leading: Vec<HiddenToken>,
+    lookahead: Option<LexicalToken>,
+    done: bool,
+}
+impl<'a> CstTokens<'a> {
+    /// Makes a new `CstTokens` over `text`.
+    pub fn new(text: &'a str) -> Self {
+        CstTokens {
+            tokenizer: Tokenizer::new(text),
+            leading: Vec::new(),
+            lookahead: None,
+            done: false,
+        }
+    }
+
+    /// Returns an adapter that skips all trivia, yielding only the significant tokens;
+    /// convenient for parser-style consumers that don't care about formatting.
+    pub fn lexical_tokens(self) -> impl Iterator<Item = Result<LexicalToken>> + 'a {
+        self.map(|r| r.map(|node| node.token))
+    }
+
+    fn next_lexical(&mut self) -> Option<Result<LexicalToken>> {
+        if let Some(token) = self.lookahead.take() {
+            return Some(Ok(token));
+        }
+        loop {
+            match self.tokenizer.next()? {
+                Ok(Token::Hidden(hidden)) => self.leading.push(hidden),
+                Ok(Token::Lexical(lexical)) => return Some(Ok(lexical)),
+                Ok(Token::Invalid(_)) => {
+                    // `CstTokens` is built on a non-recoverable `Tokenizer`, so this
+                    // cannot happen in practice; skip it rather than panicking.
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+impl<'a> Iterator for CstTokens<'a> {
+    type Item = Result<CstToken>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let token = match self.next_lexical()? {
+            Ok(token) => token,
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+        let leading = std::mem::take(&mut self.leading);
+
+        let mut trailing = Vec::new();
+        let mut after_newline = false;
+        loop {
+            match self.tokenizer.next() {
+                None => {
+                    // No token follows, so trivia after the newline stays with this one.
+                    trailing.append(&mut self.leading);
+                    break;
+                }
+                Some(Ok(Token::Lexical(next))) => {
+                    self.lookahead = Some(next);
+                    break;
+                }
+                Some(Ok(Token::Invalid(_))) => {}
+                Some(Ok(Token::Hidden(hidden))) if after_newline => self.leading.push(hidden),
+                Some(Ok(Token::Hidden(hidden))) => {
+                    after_newline = matches!(&hidden,
+                        HiddenToken::Whitespace(w) if w.value() == &Whitespace::Newline);
+                    trailing.push(hidden);
+                }
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            }
+        }
+        Some(Ok(CstToken {
+            leading,
+            token,
+            trailing,
+        }))
+    }
+}
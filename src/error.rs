@@ -0,0 +1,129 @@
+//! Error and result types.
+use std::fmt;
+use trackable::error::TrackableError;
+
+use crate::Position;
+
+/// This crate specific error type.
+#[derive(Debug, Clone, TrackableError)]
+pub struct Error(TrackableError<ErrorKind>);
+impl Error {
+    /// Returns the source position at which this error occurred, if known.
+    pub fn position(&self) -> Option<Position> {
+        self.0.kind().position()
+    }
+
+    /// Renders an annotated snippet of `source` pointing at the position of this error,
+    /// in the style of `rustc`/`ariadne`-like diagnostics:
+    ///
+    /// ```text
+    /// 1 | io:format("Hello).
+    ///               ^ unexpected end-of-string at 1:11
+    /// ```
+    ///
+    /// Falls back to `self.to_string()` when this error carries no position.
+    pub fn display_snippet(&self, source: &str) -> String {
+        match self.position() {
+            Some(position) => Diagnostic::new(position, position, self.to_string()).render(source),
+            None => self.to_string(),
+        }
+    }
+}
+
+/// A source position (or range of positions) annotated with a message, renderable as a
+/// `rustc`-style snippet via [`Diagnostic::render`].
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    start: Position,
+    end: Position,
+    message: String,
+}
+impl Diagnostic {
+    /// Makes a new `Diagnostic` spanning `[start, end)` (an empty range is allowed and is
+    /// rendered as a single-column caret).
+    pub fn new(start: Position, end: Position, message: String) -> Self {
+        Diagnostic { start, end, message }
+    }
+
+    /// Renders this diagnostic against `source`.
+    ///
+    /// The line containing `start` is printed behind a line-number gutter, followed by a
+    /// line underlining the offending span with `^` (or a `~~~` run when the span covers
+    /// more than one column), and finally the message. Tabs in the source line are
+    /// reproduced verbatim in the underline so that the caret still lines up under a
+    /// terminal's rendering of the tab; columns are counted in characters, so multibyte
+    /// text lines up correctly too. A position past the end of the text is clamped to the
+    /// last line.
+    pub fn render(&self, source: &str) -> String {
+        let lines: Vec<&str> = source.split('\n').collect();
+        let line_no = self.start.line().min(lines.len().max(1));
+        let line_text = lines.get(line_no - 1).copied().unwrap_or("");
+        let line_char_count = line_text.chars().count();
+
+        let start_col = self.start.column().min(line_char_count + 1);
+        let end_col = if self.end.line() == self.start.line() && self.end.column() > start_col {
+            self.end.column().min(line_char_count + 2)
+        } else {
+            start_col + 1
+        };
+        let underline_width = (end_col - start_col).max(1);
+
+        let gutter = format!("{} | ", line_no);
+        let mut underline = " ".repeat(gutter.len());
+        for (i, c) in line_text.chars().enumerate() {
+            if i + 1 >= start_col {
+                break;
+            }
+            underline.push(if c == '\t' { '\t' } else { ' ' });
+        }
+        if underline_width == 1 {
+            underline.push('^');
+        } else {
+            underline.extend(std::iter::repeat_n('~', underline_width));
+        }
+
+        format!("{}{}\n{} {}", gutter, line_text, underline, self.message)
+    }
+}
+
+/// The list of the possible error kinds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// Input text is invalid.
+    InvalidInput {
+        /// The position at which the invalid input starts.
+        position: Position,
+    },
+
+    /// Unexpected end-of-string.
+    UnexpectedEos {
+        /// The start position of the construct (string, atom, character literal, ...)
+        /// that the input ended inside of.
+        position: Position,
+    },
+
+    /// Other errors (e.g., an I/O error).
+    Other,
+}
+impl ErrorKind {
+    /// Returns the position associated with this error kind, if any.
+    pub fn position(&self) -> Option<Position> {
+        match *self {
+            ErrorKind::InvalidInput { position } => Some(position),
+            ErrorKind::UnexpectedEos { position } => Some(position),
+            ErrorKind::Other => None,
+        }
+    }
+}
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ErrorKind::InvalidInput { position } => write!(f, "invalid input at {}", position),
+            ErrorKind::UnexpectedEos { position } => {
+                write!(f, "unexpected end-of-string at {}", position)
+            }
+            ErrorKind::Other => write!(f, "other error"),
+        }
+    }
+}
+impl trackable::error::ErrorKind for ErrorKind {}
@@ -0,0 +1,48 @@
+//! Hidden tokens (i.e., tokens that are not meaningful to the Erlang grammar).
+use crate::position::{Position, PositionRange, Spanned};
+use crate::tokens::{Comment, Whitespace};
+
+/// Hidden token.
+///
+/// Hidden tokens (comments and whitespaces) have no effect on the semantics of an Erlang
+/// program, but a source-to-source tool still needs them to reproduce the original text.
+#[derive(Debug, Clone, PartialEq)]
+pub enum HiddenToken {
+    /// Comment token.
+    Comment(Spanned<Comment>),
+
+    /// Whitespace token.
+    Whitespace(Spanned<Whitespace>),
+}
+impl HiddenToken {
+    /// Returns this token relocated by `delta` bytes; see `Position::shifted`.
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        match *self {
+            HiddenToken::Comment(ref t) => HiddenToken::Comment(t.shifted(delta)),
+            HiddenToken::Whitespace(ref t) => HiddenToken::Whitespace(t.shifted(delta)),
+        }
+    }
+
+    /// Returns the original source text of this token.
+    pub fn text(&self) -> &str {
+        match *self {
+            HiddenToken::Comment(ref t) => t.text(),
+            HiddenToken::Whitespace(ref t) => t.text(),
+        }
+    }
+}
+impl PositionRange for HiddenToken {
+    fn start_position(&self) -> Position {
+        match *self {
+            HiddenToken::Comment(ref t) => t.start_position(),
+            HiddenToken::Whitespace(ref t) => t.start_position(),
+        }
+    }
+
+    fn end_position(&self) -> Position {
+        match *self {
+            HiddenToken::Comment(ref t) => t.end_position(),
+            HiddenToken::Whitespace(ref t) => t.end_position(),
+        }
+    }
+}
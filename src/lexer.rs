@@ -0,0 +1,492 @@
+//! The low-level, character-at-a-time scanner that backs `Tokenizer`.
+use num::BigUint;
+
+use crate::error::ErrorKind;
+use crate::position::{Position, Spanned};
+use crate::token::{InvalidToken, Token};
+use crate::tokens::{Atom, Char, Comment, Float, Int, Keyword, Sigil, Str, Symbol, TripleStr, Var};
+use crate::util;
+use crate::Result;
+
+/// A character-at-a-time scanner over an Erlang source text.
+///
+/// `Lexer` has no knowledge of recovery or incremental re-lexing; it simply produces the
+/// next `Token` (or an error) starting from its current position. `Tokenizer` is the
+/// public-facing wrapper that turns this into an `Iterator`.
+#[derive(Debug)]
+pub struct Lexer<'a> {
+    text: &'a str,
+    offset: usize,
+    position: Position,
+}
+impl<'a> Lexer<'a> {
+    /// Makes a new `Lexer` that starts scanning `text` from its beginning.
+    pub fn new(text: &'a str) -> Self {
+        Self::resume(text, 0, Position::new())
+    }
+
+    /// Makes a new `Lexer` over `text` that starts scanning from byte `offset`, reporting
+    /// `position` (which must correspond to `offset`) as the position of the first token it
+    /// produces.
+    ///
+    /// Used by `relex_incremental` to resume lexing partway through a buffer instead of
+    /// re-scanning everything that came before the resumption point.
+    pub(crate) fn resume(text: &'a str, offset: usize, position: Position) -> Self {
+        Lexer {
+            text,
+            offset,
+            position,
+        }
+    }
+
+    /// Returns the position the lexer is currently at.
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Returns the byte offset the lexer is currently at.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn peek_nth_char(&self, n: usize) -> Option<char> {
+        self.rest().chars().nth(n)
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek_char()?;
+        self.offset += c.len_utf8();
+        self.position.step_by_char(c);
+        Some(c)
+    }
+
+    fn eat_while<F>(&mut self, mut f: F)
+    where
+        F: FnMut(char) -> bool,
+    {
+        while self.peek_char().map_or(false, &mut f) {
+            self.bump();
+        }
+    }
+
+    /// Produces the next token, or `None` at the end of the input.
+    pub fn next_token(&mut self) -> Result<Option<Token>> {
+        let start = self.position;
+        let start_offset = self.offset;
+        let c = match self.peek_char() {
+            None => return Ok(None),
+            Some(c) => c,
+        };
+
+        if let Some(w) = util::as_whitespace(c) {
+            self.bump();
+            let text = self.text[start_offset..self.offset].to_owned();
+            return Ok(Some(Token::Hidden(
+                crate::hidden_token::HiddenToken::Whitespace(Spanned::new(w, text, start)),
+            )));
+        }
+        if c == '%' {
+            return Ok(Some(self.lex_comment(start)));
+        }
+        if c == '\'' {
+            return self.lex_quoted_atom(start).map(Some);
+        }
+        if c == '"' {
+            let quote_run = self.rest().chars().take_while(|&c| c == '"').count();
+            if quote_run >= 3 {
+                return self.lex_triple_str(start).map(Some);
+            }
+            return self.lex_string(start).map(Some);
+        }
+        if c == '$' {
+            return self.lex_char_literal(start).map(Some);
+        }
+        if c == '~' {
+            return self.lex_sigil(start).map(Some);
+        }
+        if c.is_ascii_digit() {
+            return self.lex_number(start).map(Some);
+        }
+        if util::is_atom_head_char(c) {
+            return Ok(Some(self.lex_atom_or_keyword(start)));
+        }
+        if util::is_variable_head_char(c) {
+            return Ok(Some(self.lex_variable(start)));
+        }
+        self.lex_symbol(start).map(Some)
+    }
+
+    fn lex_comment(&mut self, start: Position) -> Token {
+        let start_offset = self.offset;
+        self.eat_while(|c| c != '\n');
+        let text = self.text[start_offset..self.offset].to_owned();
+        Token::Hidden(crate::hidden_token::HiddenToken::Comment(Spanned::new(
+            Comment(text.clone()),
+            text,
+            start,
+        )))
+    }
+
+    fn lex_atom_or_keyword(&mut self, start: Position) -> Token {
+        let start_offset = self.offset;
+        self.eat_while(util::is_name_tail_char);
+        let text = self.text[start_offset..self.offset].to_owned();
+        if let Some(keyword) = Keyword::from_str(&text) {
+            Token::Lexical(crate::lexical_token::LexicalToken::Keyword(Spanned::new(
+                keyword, text, start,
+            )))
+        } else {
+            Token::Lexical(crate::lexical_token::LexicalToken::Atom(Spanned::new(
+                Atom(text.clone()),
+                text,
+                start,
+            )))
+        }
+    }
+
+    fn lex_variable(&mut self, start: Position) -> Token {
+        let start_offset = self.offset;
+        self.eat_while(util::is_name_tail_char);
+        let text = self.text[start_offset..self.offset].to_owned();
+        Token::Lexical(crate::lexical_token::LexicalToken::Var(Spanned::new(
+            Var(text.clone()),
+            text,
+            start,
+        )))
+    }
+
+    fn lex_quoted_atom(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.bump(); // opening quote
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+                Some('\'') => break,
+                Some('\\') => {
+                    if let Some(c) = self.bump() {
+                        value.push(unescape(c));
+                    }
+                }
+                Some(c) => value.push(c),
+            }
+        }
+        let text = self.text[start_offset..self.offset].to_owned();
+        Ok(Token::Lexical(crate::lexical_token::LexicalToken::Atom(
+            Spanned::new(Atom(value), text, start),
+        )))
+    }
+
+    fn lex_string(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.bump(); // opening quote
+        let mut value = String::new();
+        loop {
+            match self.bump() {
+                None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+                Some('"') => break,
+                Some('\\') => {
+                    if let Some(c) = self.bump() {
+                        value.push(unescape(c));
+                    }
+                }
+                Some(c) => value.push(c),
+            }
+        }
+        let text = self.text[start_offset..self.offset].to_owned();
+        Ok(Token::Lexical(crate::lexical_token::LexicalToken::Str(
+            Spanned::new(Str(value), text, start),
+        )))
+    }
+
+    fn lex_char_literal(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.bump(); // '$'
+        let value = match self.bump() {
+            None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+            Some('\\') => match self.bump() {
+                None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+                Some('^') => match self.bump() {
+                    None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+                    Some(c) => ((c as u32) & 0x1f) as u8 as char,
+                },
+                Some(c) => unescape(c),
+            },
+            Some(c) => c,
+        };
+        let text = self.text[start_offset..self.offset].to_owned();
+        Ok(Token::Lexical(crate::lexical_token::LexicalToken::Char(
+            Spanned::new(Char(value), text, start),
+        )))
+    }
+
+    fn lex_sigil(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.bump(); // '~'
+
+        let prefix_start = self.offset;
+        self.eat_while(|c| c.is_ascii_alphabetic());
+        let prefix = self.text[prefix_start..self.offset].to_owned();
+
+        let open = match self.bump() {
+            None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+            Some(c) => c,
+        };
+        let close = sigil_close(open);
+
+        let content_start = self.offset;
+        let content_end = loop {
+            match self.bump() {
+                None => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+                Some(c) if c == close => break self.offset - close.len_utf8(),
+                Some('\\') if open == '"' => {
+                    self.bump();
+                }
+                Some(_) => {}
+            }
+        };
+        let content = self.text[content_start..content_end].to_owned();
+
+        let modifier_start = self.offset;
+        self.eat_while(|c| c.is_ascii_alphanumeric());
+        let modifier = self.text[modifier_start..self.offset].to_owned();
+
+        let text = self.text[start_offset..self.offset].to_owned();
+        Ok(Token::Lexical(crate::lexical_token::LexicalToken::Sigil(
+            Spanned::new(Sigil::new(prefix, content, modifier), text, start),
+        )))
+    }
+
+    fn lex_triple_str(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.eat_while(|c| c == '"');
+        let quote_len = self.offset - start_offset;
+        let quotes = "\"".repeat(quote_len);
+
+        // The rest of the opening line (if any) is discarded uninterpreted.
+        self.eat_while(|c| c != '\n');
+        if self.peek_char() == Some('\n') {
+            self.bump();
+        }
+
+        let content_start = self.offset;
+        let (content_end, indent_len) = loop {
+            let line_start = self.offset;
+            self.eat_while(|c| c != '\n');
+            let line = &self.text[line_start..self.offset];
+            let trimmed = line.trim_start();
+            if trimmed == quotes {
+                break (line_start, line.len() - trimmed.len());
+            }
+            match self.peek_char() {
+                Some('\n') => {
+                    self.bump();
+                }
+                _ => track_panic!(ErrorKind::UnexpectedEos { position: start }),
+            }
+        };
+        let raw_content = &self.text[content_start..content_end];
+        let content = raw_content
+            .split('\n')
+            .map(|line| {
+                // `indent_len` is a byte count taken from a (possibly different) line, so it
+                // may not land on a char boundary in this one; round it down to the nearest
+                // one rather than panicking on a multibyte character straddling the cut.
+                let strip = (0..=line.len().min(indent_len))
+                    .rev()
+                    .find(|&i| line.is_char_boundary(i))
+                    .unwrap_or(0);
+                &line[strip..]
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let text = self.text[start_offset..self.offset].to_owned();
+        Ok(Token::Lexical(
+            crate::lexical_token::LexicalToken::TripleStr(Spanned::new(
+                TripleStr::new(content, quote_len),
+                text,
+                start,
+            )),
+        ))
+    }
+
+    fn lex_number(&mut self, start: Position) -> Result<Token> {
+        let start_offset = self.offset;
+        self.eat_while(|c| c.is_ascii_digit() || c == '_');
+
+        // `16#1F`-style radix integer.
+        if self.peek_char() == Some('#') {
+            self.bump();
+            self.eat_while(|c| c.is_ascii_alphanumeric() || c == '_');
+            let text = self.text[start_offset..self.offset].to_owned();
+            let digits: String = text.chars().filter(|c| *c != '_').collect();
+            let (radix_str, value_str) = digits.split_once('#').unwrap_or((&digits, ""));
+            let radix: u32 = radix_str.parse().unwrap_or(10);
+            let value = BigUint::parse_bytes(value_str.as_bytes(), radix).unwrap_or_default();
+            return Ok(Token::Lexical(crate::lexical_token::LexicalToken::Int(
+                Spanned::new(Int::new(value, radix, text.clone()), text, start),
+            )));
+        }
+
+        // Floating point: a `.` followed by at least one digit.
+        let mut is_float = false;
+        if self.peek_char() == Some('.') && self.peek_nth_char(1).map_or(false, |c| c.is_ascii_digit()) {
+            is_float = true;
+            self.bump();
+            self.eat_while(|c| c.is_ascii_digit() || c == '_');
+        }
+        if matches!(self.peek_char(), Some('e') | Some('E')) {
+            let mut n = 1;
+            if matches!(self.peek_nth_char(1), Some('+') | Some('-')) {
+                n += 1;
+            }
+            if self.peek_nth_char(n).map_or(false, |c| c.is_ascii_digit()) {
+                is_float = true;
+                self.bump();
+                if matches!(self.peek_char(), Some('+') | Some('-')) {
+                    self.bump();
+                }
+                self.eat_while(|c| c.is_ascii_digit() || c == '_');
+            }
+        }
+
+        let text = self.text[start_offset..self.offset].to_owned();
+        let digits: String = text.chars().filter(|c| *c != '_').collect();
+        if is_float {
+            let value: f64 = digits.parse().unwrap_or(0.0);
+            Ok(Token::Lexical(crate::lexical_token::LexicalToken::Float(
+                Spanned::new(Float::new(value, text.clone()), text, start),
+            )))
+        } else {
+            let value = BigUint::parse_bytes(digits.as_bytes(), 10).unwrap_or_default();
+            Ok(Token::Lexical(crate::lexical_token::LexicalToken::Int(
+                Spanned::new(Int::new(value, 10, text.clone()), text, start),
+            )))
+        }
+    }
+
+    fn lex_symbol(&mut self, start: Position) -> Result<Token> {
+        const PAIRS: &[(&str, Symbol)] = &[
+            (":=", Symbol::MapMatch),
+            ("||", Symbol::DoubleVerticalBar),
+            ("--", Symbol::MinusMinus),
+            ("++", Symbol::PlusPlus),
+            ("->", Symbol::RightAllow),
+            ("<-", Symbol::LeftAllow),
+            ("=>", Symbol::DoubleRightAllow),
+            ("<=", Symbol::DoubleLeftAllow),
+            (">>", Symbol::DoubleRightAngle),
+            ("<<", Symbol::DoubleLeftAngle),
+            ("=:=", Symbol::ExactEq),
+            ("=/=", Symbol::ExactNotEq),
+            ("==", Symbol::Eq),
+            ("/=", Symbol::NotEq),
+            (">=", Symbol::GreaterEq),
+            ("=<", Symbol::LessEq),
+        ];
+        let rest = self.rest();
+        for (pat, sym) in PAIRS {
+            if rest.starts_with(pat) {
+                for _ in 0..pat.chars().count() {
+                    self.bump();
+                }
+                let text = pat.to_string();
+                return Ok(Token::Lexical(crate::lexical_token::LexicalToken::Symbol(
+                    Spanned::new(*sym, text, start),
+                )));
+            }
+        }
+
+        let c = self.bump().expect("already peeked");
+        let sym = match c {
+            '[' => Symbol::OpenSquare,
+            ']' => Symbol::CloseSquare,
+            '(' => Symbol::OpenParen,
+            ')' => Symbol::CloseParen,
+            '{' => Symbol::OpenBrace,
+            '}' => Symbol::CloseBrace,
+            '#' => Symbol::Sharp,
+            '/' => Symbol::Slash,
+            '.' => Symbol::Dot,
+            ',' => Symbol::Comma,
+            ':' => Symbol::Colon,
+            ';' => Symbol::Semicolon,
+            '=' => Symbol::Match,
+            '|' => Symbol::VerticalBar,
+            '?' => Symbol::Question,
+            '!' => Symbol::Not,
+            '-' => Symbol::Hyphen,
+            '+' => Symbol::Plus,
+            '*' => Symbol::Multiply,
+            '>' => Symbol::Greater,
+            '<' => Symbol::Less,
+            _ => {
+                track_panic!(ErrorKind::InvalidInput { position: start });
+            }
+        };
+        Ok(Token::Lexical(crate::lexical_token::LexicalToken::Symbol(
+            Spanned::new(sym, c.to_string(), start),
+        )))
+    }
+
+    /// Skips past the offending text following a lexing error so that scanning can
+    /// continue, returning an [`InvalidToken`] covering the skipped span.
+    ///
+    /// The resync rule is: skip to (but not past) the next newline or delimiter symbol
+    /// (`. , ; ( ) { } [ ]` or whitespace), since Erlang comments are line-terminated and
+    /// top-level forms always end with `.`. When `error_position` lies before the lexer's
+    /// current position (as it does for errors, such as an unterminated string, that are
+    /// only detected at EOF), scanning is first rewound to `error_position`, so the
+    /// returned token starts there and the text after the delimiter is lexed again. At
+    /// least one character is always skipped.
+    pub fn recover(&mut self, error_position: Position) -> InvalidToken {
+        if error_position.offset() < self.offset {
+            self.offset = error_position.offset();
+            self.position = error_position;
+        }
+        let start = self.position;
+        let start_offset = self.offset;
+        if self.peek_char().is_some() {
+            self.bump();
+        }
+        self.eat_while(|c| !util::is_symbol_delimiter(c));
+        let text = self.text[start_offset..self.offset].to_owned();
+        InvalidToken::new(text, start)
+    }
+}
+
+/// Returns the delimiter that closes a sigil opened with `open`, per OTP's sigil syntax
+/// (`~"..."`, `~/.../`, `~|...|`, and the three bracket pairs).
+fn sigil_close(open: char) -> char {
+    match open {
+        '(' => ')',
+        '[' => ']',
+        '{' => '}',
+        '<' => '>',
+        other => other,
+    }
+}
+
+fn unescape(c: char) -> char {
+    match c {
+        'b' => '\u{8}',
+        'd' => '\u{7f}',
+        'e' => '\u{1b}',
+        'f' => '\u{c}',
+        'n' => '\n',
+        'r' => '\r',
+        's' => ' ',
+        't' => '\t',
+        'v' => '\u{b}',
+        other => other,
+    }
+}
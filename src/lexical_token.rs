@@ -0,0 +1,104 @@
+//! Lexical tokens (i.e., tokens that are meaningful to the Erlang grammar).
+use crate::position::{Position, PositionRange, Spanned};
+use crate::tokens::{Atom, Char, Float, Int, Keyword, Sigil, Str, Symbol, TripleStr, Var};
+
+/// Lexical token.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LexicalToken {
+    /// Atom token.
+    Atom(Spanned<Atom>),
+
+    /// Character token.
+    Char(Spanned<Char>),
+
+    /// Floating point number token.
+    Float(Spanned<Float>),
+
+    /// Integer token.
+    Int(Spanned<Int>),
+
+    /// Keyword token.
+    Keyword(Spanned<Keyword>),
+
+    /// Sigil string literal token (OTP 27+).
+    Sigil(Spanned<Sigil>),
+
+    /// String token.
+    Str(Spanned<Str>),
+
+    /// Symbol token.
+    Symbol(Spanned<Symbol>),
+
+    /// Triple- (or higher-) quoted string token (OTP 27+).
+    TripleStr(Spanned<TripleStr>),
+
+    /// Variable token.
+    Var(Spanned<Var>),
+}
+impl LexicalToken {
+    /// Returns this token relocated by `delta` bytes; see `Position::shifted`.
+    ///
+    /// Used to splice the untouched tail of a token stream back in after an
+    /// incremental re-lex (see `relex_incremental`) without re-scanning it.
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        match *self {
+            LexicalToken::Atom(ref t) => LexicalToken::Atom(t.shifted(delta)),
+            LexicalToken::Char(ref t) => LexicalToken::Char(t.shifted(delta)),
+            LexicalToken::Float(ref t) => LexicalToken::Float(t.shifted(delta)),
+            LexicalToken::Int(ref t) => LexicalToken::Int(t.shifted(delta)),
+            LexicalToken::Keyword(ref t) => LexicalToken::Keyword(t.shifted(delta)),
+            LexicalToken::Sigil(ref t) => LexicalToken::Sigil(t.shifted(delta)),
+            LexicalToken::Str(ref t) => LexicalToken::Str(t.shifted(delta)),
+            LexicalToken::Symbol(ref t) => LexicalToken::Symbol(t.shifted(delta)),
+            LexicalToken::TripleStr(ref t) => LexicalToken::TripleStr(t.shifted(delta)),
+            LexicalToken::Var(ref t) => LexicalToken::Var(t.shifted(delta)),
+        }
+    }
+
+    /// Returns the original source text of this token.
+    pub fn text(&self) -> &str {
+        match *self {
+            LexicalToken::Atom(ref t) => t.text(),
+            LexicalToken::Char(ref t) => t.text(),
+            LexicalToken::Float(ref t) => t.text(),
+            LexicalToken::Int(ref t) => t.text(),
+            LexicalToken::Keyword(ref t) => t.text(),
+            LexicalToken::Sigil(ref t) => t.text(),
+            LexicalToken::Str(ref t) => t.text(),
+            LexicalToken::Symbol(ref t) => t.text(),
+            LexicalToken::TripleStr(ref t) => t.text(),
+            LexicalToken::Var(ref t) => t.text(),
+        }
+    }
+}
+impl PositionRange for LexicalToken {
+    fn start_position(&self) -> Position {
+        match *self {
+            LexicalToken::Atom(ref t) => t.start_position(),
+            LexicalToken::Char(ref t) => t.start_position(),
+            LexicalToken::Float(ref t) => t.start_position(),
+            LexicalToken::Int(ref t) => t.start_position(),
+            LexicalToken::Keyword(ref t) => t.start_position(),
+            LexicalToken::Sigil(ref t) => t.start_position(),
+            LexicalToken::Str(ref t) => t.start_position(),
+            LexicalToken::Symbol(ref t) => t.start_position(),
+            LexicalToken::TripleStr(ref t) => t.start_position(),
+            LexicalToken::Var(ref t) => t.start_position(),
+        }
+    }
+
+    fn end_position(&self) -> Position {
+        match *self {
+            LexicalToken::Atom(ref t) => t.end_position(),
+            LexicalToken::Char(ref t) => t.end_position(),
+            LexicalToken::Float(ref t) => t.end_position(),
+            LexicalToken::Int(ref t) => t.end_position(),
+            LexicalToken::Keyword(ref t) => t.end_position(),
+            LexicalToken::Sigil(ref t) => t.end_position(),
+            LexicalToken::Str(ref t) => t.end_position(),
+            LexicalToken::Symbol(ref t) => t.end_position(),
+            LexicalToken::TripleStr(ref t) => t.end_position(),
+            LexicalToken::Var(ref t) => t.end_position(),
+        }
+    }
+}
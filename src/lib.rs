@@ -27,17 +27,18 @@
 #[macro_use]
 extern crate trackable;
 
+pub use crate::cst::{CstToken, CstTokens};
 pub use crate::error::{Error, ErrorKind};
 pub use crate::hidden_token::HiddenToken;
 pub use crate::lexer::Lexer;
 pub use crate::lexical_token::LexicalToken;
 pub use crate::position::{Position, PositionRange};
-pub use crate::token::Token;
-pub use crate::tokenizer::Tokenizer;
+pub use crate::token::{InvalidToken, Token};
+pub use crate::tokenizer::{relex_incremental, Tokenizer};
 
 pub mod tokens;
-pub mod values;
 
+mod cst;
 mod error;
 mod hidden_token;
 mod lexer;
@@ -0,0 +1,133 @@
+//! Source code positions.
+use std::fmt;
+use std::ops::Deref;
+
+/// A position in a source code text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Position {
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+impl Position {
+    /// Makes a new `Position` instance which represents the beginning of a text.
+    pub fn new() -> Self {
+        Position {
+            offset: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    /// Returns the byte offset of this position from the beginning of the text.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Returns the line number (1-based) of this position.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// Returns the column number (1-based) of this position.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Advances this position past `c`.
+    pub(crate) fn step_by_char(&mut self, c: char) {
+        self.offset += c.len_utf8();
+        if c == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+
+    /// Advances this position past every character of `text`.
+    pub(crate) fn step_by_text(&mut self, text: &str) {
+        for c in text.chars() {
+            self.step_by_char(c);
+        }
+    }
+
+    /// Returns this position with its byte offset shifted by `delta`, leaving the line
+    /// and column unchanged.
+    ///
+    /// Used by incremental re-lexing to relocate a reused, untouched tail of tokens
+    /// after an edit without having to re-scan them.
+    pub(crate) fn shifted(&self, delta: isize) -> Position {
+        Position {
+            offset: (self.offset as isize + delta).max(0) as usize,
+            ..*self
+        }
+    }
+}
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// A half-open range `[start_position(), end_position())` in a source code text.
+pub trait PositionRange {
+    /// Returns the start position of this token.
+    fn start_position(&self) -> Position;
+
+    /// Returns the end position (exclusive) of this token.
+    fn end_position(&self) -> Position;
+}
+
+/// A parsed value paired with the exact source text and start position it was lexed from.
+///
+/// Keeping the original text alongside the parsed `value` lets callers recover details
+/// (quoting, digit grouping, and the like) that parsing alone would discard.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Spanned<T> {
+    value: T,
+    text: String,
+    start: Position,
+}
+impl<T> Spanned<T> {
+    pub(crate) fn new(value: T, text: String, start: Position) -> Self {
+        Spanned { value, text, start }
+    }
+
+    /// Returns a reference to the parsed value of this token.
+    pub fn value(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns the original source text of this token.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
+impl<T> Deref for Spanned<T> {
+    type Target = T;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+impl<T: Clone> Spanned<T> {
+    /// Returns this token relocated by `delta` bytes; see `Position::shifted`.
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        Spanned {
+            value: self.value.clone(),
+            text: self.text.clone(),
+            start: self.start.shifted(delta),
+        }
+    }
+}
+impl<T> PositionRange for Spanned<T> {
+    fn start_position(&self) -> Position {
+        self.start
+    }
+
+    fn end_position(&self) -> Position {
+        let mut end = self.start;
+        end.step_by_text(&self.text);
+        end
+    }
+}
@@ -0,0 +1,117 @@
+//! Tokens.
+use crate::hidden_token::HiddenToken;
+use crate::lexical_token::LexicalToken;
+use crate::position::{Position, PositionRange};
+
+/// Token.
+///
+/// This is the item produced by `Tokenizer`'s iterator: either a `LexicalToken` (meaningful
+/// to the Erlang grammar) or a `HiddenToken` (a comment or run of whitespace).
+#[derive(Debug, Clone, PartialEq)]
+pub enum Token {
+    /// Lexical token.
+    Lexical(LexicalToken),
+
+    /// Hidden token.
+    Hidden(HiddenToken),
+
+    /// A span of source text that a recoverable `Tokenizer` could not lex.
+    ///
+    /// This variant is only ever produced when the tokenizer was put into recoverable
+    /// mode via `Tokenizer::recoverable`; see that method for details.
+    Invalid(InvalidToken),
+}
+impl Token {
+    /// Returns the original source text of this token.
+    pub fn text(&self) -> &str {
+        match *self {
+            Token::Lexical(ref t) => t.text(),
+            Token::Hidden(ref t) => t.text(),
+            Token::Invalid(ref t) => t.text(),
+        }
+    }
+
+    /// Returns `true` if this is a lexical token, otherwise `false`.
+    pub fn is_lexical(&self) -> bool {
+        matches!(*self, Token::Lexical(_))
+    }
+
+    /// Returns `true` if this is a hidden token, otherwise `false`.
+    pub fn is_hidden(&self) -> bool {
+        matches!(*self, Token::Hidden(_))
+    }
+
+    /// Returns this token relocated by `delta` bytes; see `Position::shifted`.
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        match *self {
+            Token::Lexical(ref t) => Token::Lexical(t.shifted(delta)),
+            Token::Hidden(ref t) => Token::Hidden(t.shifted(delta)),
+            Token::Invalid(ref t) => Token::Invalid(t.shifted(delta)),
+        }
+    }
+}
+impl PositionRange for Token {
+    fn start_position(&self) -> Position {
+        match *self {
+            Token::Lexical(ref t) => t.start_position(),
+            Token::Hidden(ref t) => t.start_position(),
+            Token::Invalid(ref t) => t.start_position(),
+        }
+    }
+
+    fn end_position(&self) -> Position {
+        match *self {
+            Token::Lexical(ref t) => t.end_position(),
+            Token::Hidden(ref t) => t.end_position(),
+            Token::Invalid(ref t) => t.end_position(),
+        }
+    }
+}
+impl From<LexicalToken> for Token {
+    fn from(f: LexicalToken) -> Self {
+        Token::Lexical(f)
+    }
+}
+impl From<HiddenToken> for Token {
+    fn from(f: HiddenToken) -> Self {
+        Token::Hidden(f)
+    }
+}
+
+/// A span of source text that could not be lexed.
+///
+/// Produced only by a `Tokenizer` in recoverable mode in place of aborting the token
+/// stream; see `Tokenizer::recoverable`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidToken {
+    text: String,
+    start: Position,
+}
+impl InvalidToken {
+    pub(crate) fn new(text: String, start: Position) -> Self {
+        InvalidToken { text, start }
+    }
+
+    /// Returns the offending source text covered by this token.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub(crate) fn shifted(&self, delta: isize) -> Self {
+        InvalidToken {
+            text: self.text.clone(),
+            start: self.start.shifted(delta),
+        }
+    }
+}
+impl PositionRange for InvalidToken {
+    fn start_position(&self) -> Position {
+        self.start
+    }
+
+    fn end_position(&self) -> Position {
+        let mut end = self.start;
+        end.step_by_text(&self.text);
+        end
+    }
+}
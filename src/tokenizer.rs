@@ -0,0 +1,185 @@
+//! The tokenizer.
+use std::ops::Range;
+
+use crate::lexer::Lexer;
+use crate::position::PositionRange;
+use crate::token::Token;
+use crate::{Error, Result};
+
+/// Erlang source code tokenizer.
+///
+/// By default a lexing error (an unterminated string, a bad escape, a stray backslash, ...)
+/// ends the iterator: the offending `Err` is yielded once, after which `next()` always
+/// returns `None`. Call `recoverable()` to instead keep tokenizing past errors; see that
+/// method for details.
+///
+/// # Examples
+///
+/// ```
+/// use erl_tokenize::Tokenizer;
+///
+/// let src = r#"io:format("Hello")."#;
+/// let tokenizer = Tokenizer::new(src);
+/// let tokens = tokenizer.collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(tokens.iter().map(|t| t.text()).collect::<Vec<_>>(),
+///            ["io", ":", "format", "(", r#""Hello""#, ")", "."]);
+/// ```
+#[derive(Debug)]
+pub struct Tokenizer<'a> {
+    lexer: Lexer<'a>,
+    recoverable: bool,
+    errors: Vec<Error>,
+    finished: bool,
+}
+impl<'a> Tokenizer<'a> {
+    /// Makes a new `Tokenizer` instance that will tokenize `text`.
+    pub fn new(text: &'a str) -> Self {
+        Tokenizer {
+            lexer: Lexer::new(text),
+            recoverable: false,
+            errors: Vec::new(),
+            finished: false,
+        }
+    }
+
+    /// Switches this tokenizer into recoverable mode.
+    ///
+    /// In this mode, a lexing error no longer ends the token stream. Instead the error
+    /// is recorded (see `errors`/`into_errors`) and the iterator yields a
+    /// `Token::Invalid` covering the offending text, then resynchronizes at the next
+    /// newline or delimiter symbol (`. , ; ( ) { } [ ]` or whitespace) and resumes
+    /// lexing from there. This lets a single pass collect every diagnostic in a module
+    /// instead of stopping at the first one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erl_tokenize::Tokenizer;
+    ///
+    /// let src = r#""unterminated, foo."#;
+    /// let mut tokenizer = Tokenizer::new(src).recoverable();
+    /// let tokens = (&mut tokenizer).collect::<Result<Vec<_>, _>>().unwrap();
+    /// assert!(tokens.iter().any(|t| t.text() == "foo"));
+    /// assert_eq!(tokenizer.errors().len(), 1);
+    /// ```
+    pub fn recoverable(mut self) -> Self {
+        self.recoverable = true;
+        self
+    }
+
+    /// Returns the errors collected so far while running in recoverable mode.
+    ///
+    /// Always empty unless `recoverable()` was called.
+    pub fn errors(&self) -> &[Error] {
+        &self.errors
+    }
+
+    /// Consumes this tokenizer, returning every error collected while running in
+    /// recoverable mode.
+    pub fn into_errors(self) -> Vec<Error> {
+        self.errors
+    }
+}
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = Result<Token>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.lexer.next_token() {
+            Ok(Some(token)) => Some(Ok(token)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                if self.recoverable {
+                    let start = e.position().unwrap_or_else(|| self.lexer.position());
+                    let invalid = self.lexer.recover(start);
+                    self.errors.push(e);
+                    Some(Ok(Token::Invalid(invalid)))
+                } else {
+                    self.finished = true;
+                    Some(Err(e))
+                }
+            }
+        }
+    }
+}
+
+/// Re-lexes only the minimal window affected by a single-edit change, splicing the
+/// result into the previous token vector instead of re-tokenizing the whole buffer.
+///
+/// `old_tokens` is the token vector produced for the text as it was *before* the edit,
+/// in order. `new_source` is the full text *after* the edit has already been applied.
+/// `edit` is the half-open byte range `[start, end)` of the *old* text that was replaced,
+/// and `inserted_len` is the byte length of the text that replaced it; every token after
+/// the edit is shifted by `delta = inserted_len as isize - edit.len() as isize` bytes.
+///
+/// The algorithm: locate the last old token ending at or before `edit.start`, back up one
+/// more token as a safety margin, and resume lexing `new_source` from there. Each freshly
+/// produced token is checked against the old tokens that started at or after `edit.end`
+/// (shifted by `delta`) — old tokens that started before the edit are excluded from this
+/// search, since the safety-margin token can otherwise trivially match itself before the
+/// edited span has actually been re-lexed. Once a freshly produced token matches such an
+/// old token's shifted start offset, kind and text, the untouched tail is known to still be
+/// valid and is spliced in verbatim (with every trailing token's position shifted by
+/// `delta`) instead of being re-lexed. A `\n` outside of a string or char literal is always
+/// a safe restart boundary, since no Erlang construct other than a string spans multiple
+/// lines, so in the common case this window stays small; an edit that opens or closes a
+/// quoted string will naturally cascade further, to the matching quote.
+///
+/// Returns an error (and abandons the splice) if re-lexing hits a lexing error; callers
+/// that want tolerant behavior across edits should re-lex with a `recoverable()`
+/// `Tokenizer` instead.
+pub fn relex_incremental(
+    old_tokens: &[Token],
+    new_source: &str,
+    edit: Range<usize>,
+    inserted_len: usize,
+) -> Result<Vec<Token>> {
+    let delta = inserted_len as isize - (edit.end - edit.start) as isize;
+
+    let cut = old_tokens
+        .partition_point(|t| t.end_position().offset() <= edit.start)
+        .saturating_sub(2);
+    let (prefix, rest) = old_tokens.split_at(cut);
+
+    let resume_position = rest
+        .first()
+        .map(|t| t.start_position())
+        .unwrap_or_else(crate::position::Position::new);
+    let mut lexer = Lexer::resume(new_source, resume_position.offset(), resume_position);
+
+    let mut spliced: Vec<Token> = prefix.to_vec();
+    loop {
+        let produced_offset = lexer.offset();
+        match lexer.next_token()? {
+            None => return Ok(spliced),
+            Some(token) => {
+                let old_start = token.start_position().offset() as isize - delta;
+                if old_start >= edit.end as isize {
+                    if let Some(tail_idx) = rest
+                        .iter()
+                        .position(|t| t.start_position().offset() as isize == old_start)
+                    {
+                        let old = &rest[tail_idx];
+                        if old.text() == token.text()
+                            && std::mem::discriminant(old) == std::mem::discriminant(&token)
+                        {
+                            spliced.push(token);
+                            spliced.extend(rest[tail_idx + 1..].iter().map(|t| t.shifted(delta)));
+                            return Ok(spliced);
+                        }
+                    }
+                }
+                spliced.push(token);
+                if lexer.offset() == produced_offset {
+                    // A zero-width token would otherwise loop forever.
+                    return Ok(spliced);
+                }
+            }
+        }
+    }
+}
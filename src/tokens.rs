@@ -42,6 +42,75 @@ impl Deref for Atom {
     }
 }
 
+/// Sigil string literal token (OTP 27+).
+///
+/// Covers vanilla sigils (`~"abc"`), prefixed sigils (`~b"abc"`, `~B"abc"`), and sigils
+/// written over the alternative delimiters `~/.../`, `~|...|`, `~(...)`, `~[...]`,
+/// `~{...}` and `~<...>`, each optionally followed by a trailing run of ascii-alphanumeric
+/// modifiers, e.g. the `utf8` in `~"abc"utf8`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Sigil {
+    prefix: String,
+    content: String,
+    modifier: String,
+}
+impl Sigil {
+    pub(crate) fn new(prefix: String, content: String, modifier: String) -> Self {
+        Sigil {
+            prefix,
+            content,
+            modifier,
+        }
+    }
+
+    /// Returns the sigil's prefix, e.g. `"b"` in `~b"abc"`, or `""` for a vanilla sigil.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Returns the sigil's content, i.e. the text between its delimiters.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns the sigil's trailing modifier run (ascii-alphanumeric), e.g. `"utf8"` in
+    /// `~"abc"utf8`.
+    pub fn modifier(&self) -> &str {
+        &self.modifier
+    }
+}
+
+/// Triple- (or higher-) quoted string token (OTP 27+), e.g.:
+///
+/// ```text
+/// """
+/// Hello
+/// """
+/// ```
+///
+/// No escape processing happens inside the content, and the indentation of the closing
+/// delimiter line is stripped from every content line, per the OTP rule.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct TripleStr {
+    content: String,
+    quote_len: usize,
+}
+impl TripleStr {
+    pub(crate) fn new(content: String, quote_len: usize) -> Self {
+        TripleStr { content, quote_len }
+    }
+
+    /// Returns the (de-indented) content of this string.
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    /// Returns the number of `"` characters used to delimit this string (at least 3).
+    pub fn quote_len(&self) -> usize {
+        self.quote_len
+    }
+}
+
 /// Comment token.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Comment(pub String);
@@ -84,22 +153,73 @@ impl Whitespace {
 }
 
 /// Integer token.
+///
+/// Retains the radix and the exact original text of the literal (digit grouping and
+/// all) alongside the parsed value, so a formatter can reproduce e.g. `1_6#10`
+/// verbatim while numeric consumers still get the computed `BigUint`.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Int(pub BigUint);
+pub struct Int {
+    value: BigUint,
+    radix: u32,
+    text: String,
+}
+impl Int {
+    pub(crate) fn new(value: BigUint, radix: u32, text: String) -> Self {
+        Int { value, radix, text }
+    }
+
+    /// Returns the parsed value of this integer.
+    pub fn value(&self) -> &BigUint {
+        &self.value
+    }
+
+    /// Returns the radix (2-36) this integer was written in; `10` unless it used a
+    /// `Base#`-style prefix.
+    pub fn radix(&self) -> u32 {
+        self.radix
+    }
+
+    /// Returns the original source text of this integer, e.g. `"1_6#10"`.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
 impl Deref for Int {
     type Target = BigUint;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.value
     }
 }
 
 /// Floating point number token.
+///
+/// Retains the exact original text of the literal alongside the parsed `f64`, since an
+/// `f64` is lossy and cannot by itself reproduce every source spelling (e.g. the digit
+/// grouping in `1.2_3e+1_0`, or a literal that doesn't round-trip through `f64` at all).
 #[derive(Debug, Clone, PartialEq, PartialOrd)]
-pub struct Float(pub f64);
+pub struct Float {
+    value: f64,
+    text: String,
+}
+impl Float {
+    pub(crate) fn new(value: f64, text: String) -> Self {
+        Float { value, text }
+    }
+
+    /// Returns the parsed value of this float.
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    /// Returns the original source text of this float, e.g. `"1.2_3e+1_0"`.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+}
 impl Deref for Float {
     type Target = f64;
     fn deref(&self) -> &Self::Target {
-        &self.0
+        &self.value
     }
 }
 
@@ -0,0 +1,39 @@
+//! Miscellaneous helper functions shared by the lexer.
+use crate::tokens::Whitespace;
+
+/// Returns `true` if `c` starts an atom or keyword written without quotes.
+pub fn is_atom_head_char(c: char) -> bool {
+    c.is_lowercase() && c.is_alphabetic()
+}
+
+/// Returns `true` if `c` starts a variable (or the `_` wildcard).
+pub fn is_variable_head_char(c: char) -> bool {
+    c == '_' || (c.is_uppercase() && c.is_alphabetic())
+}
+
+/// Returns `true` if `c` may appear after the first character of an atom or variable name.
+pub fn is_name_tail_char(c: char) -> bool {
+    c == '_' || c == '@' || c.is_alphanumeric()
+}
+
+/// Returns the `Whitespace` kind of `c`, or `None` if `c` is not one of the whitespace
+/// characters this crate recognizes.
+pub fn as_whitespace(c: char) -> Option<Whitespace> {
+    match c {
+        ' ' => Some(Whitespace::Space),
+        '\t' => Some(Whitespace::Tab),
+        '\r' => Some(Whitespace::Return),
+        '\n' => Some(Whitespace::Newline),
+        '\u{A0}' => Some(Whitespace::NoBreakSpace),
+        _ => None,
+    }
+}
+
+/// Returns `true` if `c` is a delimiter that error recovery resynchronizes at: one of
+/// `. , ; ( ) { } [ ]` or a whitespace character.
+pub fn is_symbol_delimiter(c: char) -> bool {
+    matches!(
+        c,
+        '.' | ',' | ';' | '(' | ')' | '{' | '}' | '[' | ']'
+    ) || as_whitespace(c).is_some()
+}
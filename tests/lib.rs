@@ -1,4 +1,4 @@
-use erl_tokenize::Tokenizer;
+use erl_tokenize::{relex_incremental, CstTokens, LexicalToken, Tokenizer};
 
 macro_rules! tokenize {
     ($text:expr) => {
@@ -70,6 +70,62 @@ fn tokenize_chars() {
     );
 }
 
+#[test]
+fn tokenize_sigils() {
+    let src = r#"~"abc" ~b"abc"utf8 ~B"abc" ~/abc/ ~|abc| ~(abc) ~[abc] ~{abc} ~<abc>"#;
+    assert_eq!(
+        tokenize!(src),
+        [
+            r#"~"abc""#,
+            " ",
+            r#"~b"abc"utf8"#,
+            " ",
+            r#"~B"abc""#,
+            " ",
+            "~/abc/",
+            " ",
+            "~|abc|",
+            " ",
+            "~(abc)",
+            " ",
+            "~[abc]",
+            " ",
+            "~{abc}",
+            " ",
+            "~<abc>",
+        ]
+    );
+}
+
+#[test]
+fn tokenize_triple_quoted_strings() {
+    let src = "\"\"\"\nhello\n\"\"\"";
+    assert_eq!(tokenize!(src), [src]);
+}
+
+#[test]
+fn int_and_float_tokens_preserve_their_original_text() {
+    let src = "1_6#10 1.2_3e+1_0";
+    let tokens = Tokenizer::new(src)
+        .filter_map(|t| match t.unwrap() {
+            erl_tokenize::Token::Lexical(t) => Some(t),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    match &tokens[0] {
+        LexicalToken::Int(i) => {
+            assert_eq!(i.radix(), 16);
+            assert_eq!(i.text(), "1_6#10");
+        }
+        other => panic!("expected an integer token, got {:?}", other),
+    }
+    match &tokens[1] {
+        LexicalToken::Float(f) => assert_eq!(f.text(), "1.2_3e+1_0"),
+        other => panic!("expected a float token, got {:?}", other),
+    }
+}
+
 #[test]
 fn tokenize_module_declaration() {
     let src = "-module(foo).";
@@ -81,3 +137,62 @@ fn tokenize_multibyte_whitespaces() {
     let src = "a\u{a0}b";
     assert_eq!(tokenize!(src), ["a", "\u{a0}", "b"]);
 }
+
+#[test]
+fn error_display_snippet_points_at_the_failure() {
+    let src = "foo(\"bar).";
+    let err = Tokenizer::new(src)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap_err();
+    let rendered = err.display_snippet(src);
+    assert!(rendered.contains("foo(\"bar)."));
+    assert!(rendered.contains('^'));
+}
+
+#[test]
+fn cst_tokens_round_trip_the_source() {
+    let src = "-module(foo). % trailing comment\n% leading comment\nbar() -> ok.";
+    let nodes = CstTokens::new(src).collect::<Result<Vec<_>, _>>().unwrap();
+    let rebuilt = nodes.iter().map(|n| n.to_source()).collect::<String>();
+    assert_eq!(rebuilt, src);
+
+    let module_node = &nodes[0];
+    assert_eq!(module_node.token().text(), "-");
+    let dot_node = nodes.iter().find(|n| n.token().text() == ".").unwrap();
+    assert!(dot_node
+        .trailing_trivia()
+        .iter()
+        .any(|t| t.text() == "% trailing comment"));
+}
+
+#[test]
+fn relex_incremental_reuses_the_untouched_tail() {
+    let old_src = "foo(bar, 1).";
+    let old_tokens = Tokenizer::new(old_src)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    // Replace `bar` with `baz`; everything from the `,` onward is untouched.
+    let new_src = "foo(baz, 1).";
+    let new_tokens = relex_incremental(&old_tokens, new_src, 4..7, 3).unwrap();
+
+    assert_eq!(
+        new_tokens.iter().map(|t| t.text()).collect::<Vec<_>>(),
+        tokenize!(new_src)
+    );
+}
+
+#[test]
+fn recoverable_tokenizer_collects_every_error() {
+    // `@` isn't a valid token head, so each one is an independent lexing error that
+    // resyncs at the next delimiter rather than only failing once at end-of-input (as
+    // an unterminated string or char literal would).
+    let src = "-module(foo). @ bar, @ baz.";
+    let mut tokenizer = Tokenizer::new(src).recoverable();
+    let texts = (&mut tokenizer)
+        .map(|t| t.unwrap().text().to_string())
+        .collect::<Vec<_>>();
+    assert!(texts.iter().any(|t| t.contains("bar")));
+    assert!(texts.iter().any(|t| t.contains("baz")));
+    assert_eq!(tokenizer.errors().len(), 2);
+}